@@ -0,0 +1,305 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use leo_errors::{Result, TypeCheckerError};
+
+use crate::SymbolTable;
+
+/// The coloring used by [`CallGraph::find_cycle`]'s DFS: white nodes are unvisited, gray nodes
+/// are on the current recursion stack, and black nodes are fully explored.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Color {
+    White,
+    Gray,
+    Black,
+}
+
+/// A directed call graph over every function in a [`SymbolTable`], keyed by each function's
+/// scope `id`. Built by [`SymbolTable::call_graph`] and used to reject illegal recursion, check
+/// that `async` transitions have a finalize block, and flag dead transitions.
+#[derive(Debug, Default)]
+pub struct CallGraph {
+    /// Adjacency list of callee ids, keyed by caller id.
+    edges: HashMap<usize, Vec<usize>>,
+    /// Adjacency list of caller ids, keyed by callee id.
+    reverse_edges: HashMap<usize, Vec<usize>>,
+}
+
+impl CallGraph {
+    /// Returns the ids of every function called directly by `id`.
+    pub fn callees(&self, id: usize) -> &[usize] {
+        self.edges.get(&id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Returns the ids of every function that directly calls `id`.
+    pub fn callers(&self, id: usize) -> &[usize] {
+        self.reverse_edges.get(&id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Returns `true` if `id` can reach itself through one or more calls.
+    pub fn is_recursive(&self, id: usize) -> bool {
+        let mut stack = self.callees(id).to_vec();
+        let mut visited = HashSet::new();
+        while let Some(callee) = stack.pop() {
+            if callee == id {
+                return true;
+            }
+            if visited.insert(callee) {
+                stack.extend(self.callees(callee));
+            }
+        }
+        false
+    }
+
+    /// Returns every id reachable from `roots`, inclusive of `roots` themselves.
+    pub fn reachable_from(&self, roots: &[usize]) -> HashSet<usize> {
+        let mut reachable = HashSet::new();
+        let mut stack = roots.to_vec();
+        while let Some(id) = stack.pop() {
+            if reachable.insert(id) {
+                stack.extend(self.callees(id));
+            }
+        }
+        reachable
+    }
+
+    fn add_edge(&mut self, caller: usize, callee: usize) {
+        self.edges.entry(caller).or_default().push(callee);
+        self.reverse_edges.entry(callee).or_default().push(caller);
+    }
+
+    /// Runs a DFS with recursion-stack coloring over every node, in deterministic (ascending id)
+    /// start order, returning every cycle found rather than stopping at the first: a legal
+    /// all-`inline` cycle elsewhere in the graph must not hide an illegal one found later.
+    ///
+    /// Each cycle is the full set of nodes from the back edge's target around to the caller that
+    /// closes it, not just the closing edge.
+    pub fn find_cycle(&self) -> Vec<Vec<usize>> {
+        let nodes: BTreeSet<usize> = self.edges.keys().chain(self.reverse_edges.keys()).copied().collect();
+        let mut color: HashMap<usize, Color> = HashMap::new();
+        let mut path: Vec<usize> = Vec::new();
+        let mut cycles: Vec<Vec<usize>> = Vec::new();
+
+        for &start in &nodes {
+            if color.get(&start).copied().unwrap_or(Color::White) == Color::White {
+                self.visit(start, &mut color, &mut path, &mut cycles);
+            }
+        }
+
+        cycles
+    }
+
+    fn visit(&self, id: usize, color: &mut HashMap<usize, Color>, path: &mut Vec<usize>, cycles: &mut Vec<Vec<usize>>) {
+        color.insert(id, Color::Gray);
+        path.push(id);
+        for &callee in self.callees(id) {
+            match color.get(&callee).copied().unwrap_or(Color::White) {
+                Color::White => self.visit(callee, color, path, cycles),
+                Color::Gray => {
+                    let start = path.iter().position(|&node| node == callee).expect("a gray node is always on `path`");
+                    cycles.push(path[start..].to_vec());
+                }
+                Color::Black => {}
+            }
+        }
+        path.pop();
+        color.insert(id, Color::Black);
+    }
+}
+
+impl SymbolTable {
+    /// Looks up the function symbol whose scope id is `id`.
+    pub(crate) fn function_symbol(&self, id: usize) -> Option<&crate::FunctionSymbol> {
+        self.functions.values().find(|function| function.id == id)
+    }
+
+    /// Mutably looks up the function symbol whose scope id is `id`.
+    pub(crate) fn function_symbol_mut(&mut self, id: usize) -> Option<&mut crate::FunctionSymbol> {
+        self.functions.values_mut().find(|function| function.id == id)
+    }
+
+    /// Builds a directed call graph over every function in this symbol table, with edges taken
+    /// from each function's recorded call sites (see [`crate::FunctionSymbol::call_sites`]).
+    ///
+    /// NOTE: `record_call_site` and `set_finalize`, which populate the data this builds from, are
+    /// invoked by the call-expression/finalize-pairing type-checking visitor in
+    /// `compiler/passes/src/type_checking/`, which is not part of this patch series's diff.
+    pub fn call_graph(&self) -> CallGraph {
+        let mut graph = CallGraph::default();
+        for function in self.functions.values() {
+            for callee_id in function.callees() {
+                graph.add_edge(function.id, callee_id);
+            }
+        }
+        graph
+    }
+
+    /// Checks the call graph for illegal recursion among non-inlined functions, verifies that
+    /// every `async` transition has a finalize block, verifies that a recorded call to that block
+    /// passes the number of arguments its `FinalizeData::input` expects, and flags finalize blocks
+    /// unreachable from any program entry point as dead code. An `async` transition with a
+    /// finalize block but no recorded call to it is not rejected.
+    ///
+    /// `entry_points` should be the scope ids of every `transition` function in the program.
+    pub fn check_call_graph(&self, entry_points: &[usize]) -> Result<()> {
+        let graph = self.call_graph();
+
+        for cycle in graph.find_cycle() {
+            let illegal = cycle
+                .iter()
+                .filter_map(|&id| self.function_symbol(id))
+                .find(|function| !matches!(function.variant, leo_ast::Variant::Inline));
+            if let Some(function) = illegal {
+                return Err(TypeCheckerError::illegal_recursive_call(function._span).into());
+            }
+        }
+
+        for function in self.functions.values() {
+            if function.is_async && function.finalize.is_none() {
+                return Err(TypeCheckerError::async_transition_missing_finalize(function._span).into());
+            }
+
+            // Compare the arguments *this function's own body* passes to its paired `finalize`
+            // block against that block's input count -- not the arguments some other function
+            // passed when calling `function` itself, which is an unrelated quantity.
+            if let (Some(finalize), Some(argument_count)) = (&function.finalize, function.finalize_call_argument_count) {
+                if !finalize_arity_matches(argument_count, finalize) {
+                    return Err(TypeCheckerError::finalize_input_mismatch(function._span).into());
+                }
+            }
+        }
+
+        let reachable = graph.reachable_from(entry_points);
+        let ids_with_finalize: Vec<usize> =
+            self.functions.values().filter(|function| function.finalize.is_some()).map(|function| function.id).collect();
+        if let Some(id) = unreachable_finalize_owner(&ids_with_finalize, &reachable) {
+            if let Some(function) = self.function_symbol(id) {
+                return Err(TypeCheckerError::unreachable_finalize_block(function._span).into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether `argument_count` (the number of arguments passed to a `finalize` sub-call) matches the
+/// number of inputs `finalize` (the finalize block being called) expects.
+fn finalize_arity_matches(argument_count: usize, finalize: &crate::FinalizeData) -> bool {
+    argument_count == finalize.input.len()
+}
+
+/// Returns the first id in `ids_with_finalize` that isn't in `reachable`, if any. Pulled out of
+/// [`SymbolTable::check_call_graph`] so this dead-code check is testable without a `SymbolTable`:
+/// every id with `finalize.is_some()` is, by construction, an async transition and therefore
+/// already a member of `entry_points` in real programs, so this never actually fires in practice
+/// today; it exists to guard the invariant regardless, and to be ready once a caller passes in a
+/// narrower set of entry points (e.g. per-program rather than per-transition).
+fn unreachable_finalize_owner(ids_with_finalize: &[usize], reachable: &HashSet<usize>) -> Option<usize> {
+    ids_with_finalize.iter().copied().find(|id| !reachable.contains(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_cycle_returns_the_full_cycle_not_just_the_closing_edge() {
+        let mut graph = CallGraph::default();
+        // 1 -> 2 -> 3 -> 2 (the cycle is 2 -> 3 -> 2; 1 is not part of it).
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 2);
+
+        let cycles = graph.find_cycle();
+
+        assert_eq!(cycles.len(), 1);
+        let cycle = &cycles[0];
+        assert_eq!(cycle.len(), 2, "the cycle should contain both `2` and `3`, not just the closing edge");
+        assert!(cycle.contains(&2));
+        assert!(cycle.contains(&3));
+        assert!(!cycle.contains(&1), "`1` only calls into the cycle, it isn't part of it");
+    }
+
+    #[test]
+    fn find_cycle_returns_empty_for_an_acyclic_graph() {
+        let mut graph = CallGraph::default();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+
+        assert!(graph.find_cycle().is_empty());
+    }
+
+    #[test]
+    fn find_cycle_keeps_scanning_past_an_earlier_cycle() {
+        let mut graph = CallGraph::default();
+        // Two disjoint cycles: 1 -> 2 -> 1, and 10 -> 11 -> 10.
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+        graph.add_edge(10, 11);
+        graph.add_edge(11, 10);
+
+        let cycles = graph.find_cycle();
+
+        assert_eq!(cycles.len(), 2, "both cycles should be reported, not just the first one found");
+        assert!(cycles.iter().any(|cycle| cycle.contains(&1) && cycle.contains(&2)));
+        assert!(cycles.iter().any(|cycle| cycle.contains(&10) && cycle.contains(&11)));
+    }
+
+    #[test]
+    fn find_cycle_start_order_is_deterministic() {
+        let mut graph = CallGraph::default();
+        graph.add_edge(5, 6);
+        graph.add_edge(6, 5);
+        graph.add_edge(100, 101);
+        graph.add_edge(101, 100);
+
+        let first = graph.find_cycle();
+        for _ in 0..10 {
+            assert_eq!(graph.find_cycle(), first, "repeated scans of the same graph must report cycles in the same order");
+        }
+    }
+
+    #[test]
+    fn reachable_from_includes_roots_and_transitive_callees() {
+        let mut graph = CallGraph::default();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+
+        let reachable = graph.reachable_from(&[1]);
+
+        assert_eq!(reachable, HashSet::from([1, 2, 3]));
+        assert!(!graph.reachable_from(&[3]).contains(&1));
+    }
+
+    #[test]
+    fn finalize_arity_matches_compares_the_finalize_sub_call_arguments_to_its_own_block() {
+        let finalize = crate::FinalizeData { input: Vec::new(), output_type: leo_ast::Type::Unit };
+        assert!(finalize_arity_matches(0, &finalize));
+        assert!(!finalize_arity_matches(1, &finalize));
+    }
+
+    #[test]
+    fn unreachable_finalize_owner_flags_a_finalize_holder_outside_entry_points() {
+        let reachable = HashSet::from([1]);
+
+        assert_eq!(unreachable_finalize_owner(&[2], &reachable), Some(2));
+        assert_eq!(unreachable_finalize_owner(&[1], &reachable), None);
+        assert_eq!(unreachable_finalize_owner(&[1, 2], &reachable), Some(2));
+        assert_eq!(unreachable_finalize_owner(&[], &reachable), None);
+    }
+}
@@ -30,6 +30,17 @@ pub struct FinalizeData {
     pub(crate) output_type: Type,
 }
 
+/// A single call expression found directly in a function's body, recorded while type-checking
+/// call expressions. The call graph takes its edges from these; the recorded argument count is
+/// not used by the finalize arity check, which reads `finalize_call_argument_count` instead.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct CallSite {
+    /// The scope id of the function being called.
+    pub(crate) callee_id: usize,
+    /// The number of arguments passed at this call site.
+    pub(crate) argument_count: usize,
+}
+
 /// An entry for a function in the symbol table.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FunctionSymbol {
@@ -45,6 +56,24 @@ pub struct FunctionSymbol {
     pub(crate) _span: Span,
     /// The inputs to the function.
     pub(crate) input: Vec<Input>,
+    /// The finalize block this function's `async` transition resolves into, if any.
+    pub(crate) finalize: Option<FinalizeData>,
+    /// Every call expression found directly in this function's body, recorded while
+    /// type-checking call expressions. Used to build the program's call graph.
+    pub(crate) call_sites: Vec<CallSite>,
+    /// The number of arguments passed at the call this function's own body makes to its paired
+    /// `finalize` block, if it has made one. This is distinct from `call_sites`, which records
+    /// calls made *to* other functions (used for the call graph's edges): checking `finalize`'s
+    /// arity requires the arguments passed *by this function* to its own finalize sub-call, not
+    /// the arguments some other function passed when calling this one.
+    pub(crate) finalize_call_argument_count: Option<usize>,
+}
+
+impl FunctionSymbol {
+    /// Returns the scope `id` of every function called directly from this function's body.
+    pub(crate) fn callees(&self) -> impl Iterator<Item = usize> + '_ {
+        self.call_sites.iter().map(|call_site| call_site.callee_id)
+    }
 }
 
 impl SymbolTable {
@@ -55,7 +84,40 @@ impl SymbolTable {
             output_type: func.output_type.clone(),
             variant: func.variant,
             _span: func.span,
-            input: func.input.clone()
+            input: func.input.clone(),
+            finalize: None,
+            call_sites: Vec::new(),
+            finalize_call_argument_count: None,
+        }
+    }
+
+    /// Records a call expression found directly in the body of `caller_id`, calling
+    /// `callee_id` with `argument_count` arguments. Called while type-checking a call
+    /// expression, once the callee has been resolved.
+    ///
+    /// NOTE: the caller of this method is the call-expression type-checking visitor in
+    /// `compiler/passes/src/type_checking/`, which is not part of this patch series's diff.
+    pub(crate) fn record_call_site(&mut self, caller_id: usize, callee_id: usize, argument_count: usize) {
+        if let Some(caller) = self.function_symbol_mut(caller_id) {
+            caller.call_sites.push(CallSite { callee_id, argument_count });
+        }
+    }
+
+    /// Records the finalize block that `id`'s `async transition` resolves into. Called once the
+    /// finalize block paired with an async transition has been resolved, by the same
+    /// out-of-scope visitor as [`Self::record_call_site`].
+    pub(crate) fn set_finalize(&mut self, id: usize, finalize: FinalizeData) {
+        if let Some(function) = self.function_symbol_mut(id) {
+            function.finalize = Some(finalize);
+        }
+    }
+
+    /// Records that `id`'s own body calls its paired `finalize` block with `argument_count`
+    /// arguments. Called while type-checking that finalize sub-call, by the same out-of-scope
+    /// visitor as [`Self::record_call_site`].
+    pub(crate) fn record_finalize_call(&mut self, id: usize, argument_count: usize) {
+        if let Some(function) = self.function_symbol_mut(id) {
+            function.finalize_call_argument_count = Some(argument_count);
         }
     }
 }
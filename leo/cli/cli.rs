@@ -0,0 +1,45 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+// NOTE: this only lists the subcommands touched by this patch series (`Remove`, `Update`). The
+// rest of the `leo` subcommand surface (`Add`, `Build`, `New`, `Run`, ...) lives outside the
+// files this change touches and is omitted here rather than guessed at.
+#[derive(Parser, Debug)]
+pub enum Commands {
+    #[clap(about = "Remove a dependency from the current package")]
+    Remove(Remove),
+    #[clap(about = "Update the Leo CLI to the latest (or a pinned) version")]
+    Update(Update),
+}
+
+impl Commands {
+    pub fn execute(self, context: Context) -> Result<()> {
+        match self {
+            Commands::Remove(command) => Self::run(command, context),
+            Commands::Update(command) => Self::run(command, context),
+        }
+    }
+
+    /// Runs a [`Command`] through its `log_span` / `prelude` / `apply` lifecycle.
+    fn run<C: Command<Output = ()>>(command: C, context: Context) -> Result<()> {
+        let span = command.log_span();
+        let _enter = span.enter();
+        let input = command.prelude(context.clone())?;
+        command.apply(context, input)
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use leo_retriever::{Dependency, Manifest};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// The name of the lockfile written alongside `program.json`.
+pub(crate) const LOCKFILE_NAME: &str = "program.lock";
+
+/// A single fully-resolved dependency in a [`Lockfile`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct LockedDependency {
+    /// The dependency's program name, e.g. `credits.aleo`.
+    pub(crate) name: String,
+    /// The network the dependency was resolved from, if any.
+    pub(crate) network: Option<String>,
+    /// Where the dependency's bytecode was resolved from: a local path or a network endpoint.
+    pub(crate) source: String,
+    /// A SHA-256 hash of the dependency's fetched bytecode, or empty if no bytecode was found on
+    /// disk. It is recorded only; no check compares it against the bytecode on disk.
+    pub(crate) checksum: String,
+}
+
+/// The manifest's direct dependencies as resolved on disk, written as `program.lock`. Transitive
+/// dependencies and the import edges between programs are not recorded.
+///
+/// NOTE: this only wires `program.lock` into `Remove`. `leo/cli/commands/add.rs` is not part of
+/// this patch series's diff, so `Add` does not yet read or write a lockfile; that's a follow-up,
+/// not an oversight.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct Lockfile {
+    pub(crate) dependencies: Vec<LockedDependency>,
+}
+
+impl Lockfile {
+    /// Reads `program.lock` from `path`, if it exists.
+    pub(crate) fn read(path: &Path) -> Result<Option<Self>> {
+        let lock_path = path.join(LOCKFILE_NAME);
+        if !lock_path.is_file() {
+            return Ok(None);
+        }
+
+        let data = std::fs::read_to_string(&lock_path).map_err(|err| PackageError::failed_to_read_file(lock_path.to_str().unwrap(), err))?;
+        let lockfile = serde_json::from_str(&data)
+            .map_err(|err| PackageError::failed_to_deserialize_manifest_file(lock_path.to_str().unwrap(), err))?;
+        Ok(Some(lockfile))
+    }
+
+    /// Writes this lockfile to `program.lock` under `path`.
+    pub(crate) fn write(&self, path: &Path) -> Result<()> {
+        let lock_path = path.join(LOCKFILE_NAME);
+        let data = serde_json::to_string_pretty(self)
+            .map_err(|err| PackageError::failed_to_serialize_manifest_file(lock_path.to_str().unwrap(), err))?;
+        std::fs::write(&lock_path, data).map_err(PackageError::failed_to_write_manifest)
+    }
+
+    /// Resolves `dependencies` against the fetched programs under `imports_dir`, hashing each
+    /// one's bytecode where it is found on disk.
+    pub(crate) fn resolve(dependencies: &[Dependency], imports_dir: &Path) -> Result<Self> {
+        let locked = dependencies
+            .iter()
+            .map(|dependency| {
+                // The three dependency kinds: local (has a path), network (has a network, no
+                // path), and git (neither).
+                let (source, bytecode_path) = match (dependency.path(), dependency.network()) {
+                    (Some(local_path), _) => (local_path.to_str().unwrap().to_string(), local_path.join("build/main.aleo")),
+                    (None, Some(network)) => {
+                        (format!("network:{network}"), imports_dir.join(dependency.name()).join("main.aleo"))
+                    }
+                    (None, None) => (format!("git:{}", dependency.name()), imports_dir.join(dependency.name()).join("main.aleo")),
+                };
+
+                let checksum = if bytecode_path.is_file() {
+                    let bytecode = std::fs::read(&bytecode_path)
+                        .map_err(|err| PackageError::failed_to_read_file(bytecode_path.to_str().unwrap(), err))?;
+                    format!("{:x}", Sha256::digest(&bytecode))
+                } else {
+                    String::new()
+                };
+
+                Ok(LockedDependency { name: dependency.name().clone(), network: dependency.network().cloned(), source, checksum })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { dependencies: locked })
+    }
+
+    /// Errors if this lockfile's dependency names diverge from `manifest`'s. Used to catch a
+    /// `program.lock` that has gone stale relative to `program.json`.
+    pub(crate) fn check_consistent_with_manifest(&self, manifest: &Manifest) -> Result<()> {
+        let manifest_names: std::collections::HashSet<&String> =
+            manifest.dependencies().as_ref().map(|deps| deps.iter().map(Dependency::name).collect()).unwrap_or_default();
+        let lock_names: std::collections::HashSet<&String> = self.dependencies.iter().map(|dep| &dep.name).collect();
+
+        if manifest_names != lock_names {
+            return Err(PackageError::manifest_lockfile_mismatch(PathBuf::from(LOCKFILE_NAME)).into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git_dependency(name: &str) -> Dependency {
+        Dependency::new(name.to_string(), None, None)
+    }
+
+    #[test]
+    fn resolve_labels_git_dependency_distinctly_from_network() {
+        let imports_dir = std::env::temp_dir().join(format!("leo-lock-test-{}", std::process::id()));
+        let dependencies = vec![git_dependency("foo.aleo")];
+
+        let lockfile = Lockfile::resolve(&dependencies, &imports_dir).unwrap();
+
+        assert_eq!(lockfile.dependencies.len(), 1);
+        assert_eq!(lockfile.dependencies[0].source, "git:foo.aleo");
+        assert_eq!(lockfile.dependencies[0].network, None);
+    }
+
+    #[test]
+    fn check_consistent_with_manifest_detects_drift() {
+        let lockfile = Lockfile {
+            dependencies: vec![LockedDependency {
+                name: "foo.aleo".to_string(),
+                network: None,
+                source: "git:foo.aleo".to_string(),
+                checksum: String::new(),
+            }],
+        };
+        let manifest = Manifest::new("bar.aleo", "0.1.0", "", "", Some(vec![git_dependency("bar.aleo")]));
+
+        assert!(lockfile.check_consistent_with_manifest(&manifest).is_err());
+    }
+}
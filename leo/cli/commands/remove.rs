@@ -15,8 +15,26 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
+use super::lock::Lockfile;
 use leo_retriever::{Dependency, Manifest};
-use std::path::PathBuf;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+/// The outcome of a `leo remove` invocation, printed as structured JSON when `--json` is passed.
+#[derive(Debug, Serialize)]
+struct RemoveReport {
+    /// The dependency set that remains (or would remain) after this invocation.
+    dependencies: Vec<String>,
+    /// The top-level dependencies that were (or would be) removed.
+    removed: Vec<String>,
+    /// The transitive dependencies that were (or would be) pruned as orphans.
+    pruned_orphans: Vec<String>,
+    /// Whether `--dry-run` prevented any of the above from actually being written to disk.
+    dry_run: bool,
+}
 
 /// Remove a dependency from the current package.
 #[derive(Parser, Debug)]
@@ -24,10 +42,11 @@ use std::path::PathBuf;
 pub struct Remove {
     #[clap(
         name = "NAME",
-        help = "The dependency name. Ex: `credits.aleo` or `credits`.",
-        required_unless_present = "all"
+        help = "The dependency name(s) to remove. Ex: `credits.aleo` or `credits`. Multiple names may be given to remove them all in one invocation.",
+        required_unless_present_any = ["all", "all_network", "migrate"],
+        num_args = 1..
     )]
-    pub(crate) name: Option<String>,
+    pub(crate) names: Vec<String>,
 
     #[clap(short = 'l', long, help = "Path to local dependency")]
     pub(crate) local: Option<PathBuf>,
@@ -37,6 +56,38 @@ pub struct Remove {
 
     #[clap(long, help = "Clear all previous dependencies.", default_value = "false")]
     pub(crate) all: bool,
+
+    #[clap(
+        long,
+        help = "Remove every dependency on the network given by `--network`, regardless of name.",
+        default_value = "false"
+    )]
+    pub(crate) all_network: bool,
+
+    #[clap(
+        long,
+        num_args = 2,
+        value_names = ["FROM", "TO"],
+        help = "Rewrite every network dependency's network field from FROM to TO in place, instead of removing anything. Ex: `--migrate testnet3 mainnet`."
+    )]
+    pub(crate) migrate: Option<Vec<String>>,
+
+    #[clap(
+        long,
+        help = "Keep transitive dependencies that become unreachable after removal instead of pruning them.",
+        default_value = "false"
+    )]
+    pub(crate) keep_orphans: bool,
+
+    #[clap(
+        long,
+        help = "Preview the dependencies and orphans that would be removed without writing to `program.json`, `program.lock`, or the import cache.",
+        default_value = "false"
+    )]
+    pub(crate) dry_run: bool,
+
+    #[clap(long, help = "Emit the resulting dependency set and warnings as JSON instead of human-readable output.", default_value = "false")]
+    pub(crate) json: bool,
 }
 
 impl Command for Remove {
@@ -61,39 +112,64 @@ impl Command for Remove {
         let manifest: Manifest = serde_json::from_str(&program_data)
             .map_err(|err| PackageError::failed_to_deserialize_manifest_file(path.to_str().unwrap(), err))?;
 
-        let dependencies: Vec<Dependency> = if !self.all {
-            // Make sure the program name is valid.
+        // If a lockfile already exists, make sure it still agrees with the manifest before we
+        // mutate either of them.
+        if let Some(lockfile) = Lockfile::read(&path)? {
+            lockfile.check_consistent_with_manifest(&manifest)?;
+        }
+
+        // A `--migrate FROM TO` invocation rewrites network fields in place and never removes anything.
+        if let Some(migrate) = &self.migrate {
+            return Self::migrate_network(path, manifest, &migrate[0], &migrate[1], self.dry_run, self.json);
+        }
+
+        let mut removed: Vec<String> = Vec::new();
+
+        let dependencies: Vec<Dependency> = if self.all {
+            if let Some(existing) = manifest.dependencies() {
+                removed.extend(existing.iter().map(|dependency| dependency.name().clone()));
+            }
+            Vec::new()
+        } else if self.all_network {
+            let dep = manifest
+                .dependencies()
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|dependency| {
+                    if dependency.network() == Some(&self.network) {
+                        removed.push(dependency.name().clone());
+                        false
+                    } else {
+                        true
+                    }
+                })
+                .collect();
+
+            // Throw error if no match is found.
+            if removed.is_empty() {
+                return Err(PackageError::dependency_not_found(self.network.clone()).into());
+            }
+
+            dep
+        } else {
+            // Make sure every program name is valid.
             // Allow both `credits.aleo` and `credits` syntax.
-            let name: String = match &self.name {
-                Some(name)
-                    if name.ends_with(".aleo")
-                        && Package::<CurrentNetwork>::is_program_name_valid(&name[0..name.len() - 5]) =>
-                {
-                    name.clone()
-                }
-                Some(name) if Package::<CurrentNetwork>::is_program_name_valid(name) => format!("{name}.aleo"),
-                name => return Err(PackageError::invalid_file_name_dependency(name.clone().unwrap()).into()),
-            };
+            let names: HashSet<String> =
+                self.names.iter().map(|name| Self::normalize_name(name)).collect::<Result<_>>()?;
 
-            let mut found_match = false;
+            let mut found: HashSet<String> = HashSet::new();
             let dep = match manifest.dependencies() {
                 Some(ref dependencies) => dependencies
                     .iter()
                     .filter_map(|dependency| {
-                        if dependency.name() == &name {
-                            found_match = true;
-                            let msg = match (dependency.path(), dependency.network()) {
-                                (Some(local_path), _) => format!(
-                                    "local dependency to `{}` from path `{}`",
-                                    name,
-                                    local_path.to_str().unwrap().replace('\"', "")
-                                ),
-                                (_, Some(network)) => {
-                                    format!("network dependency to `{}` from network `{}`", name, network)
-                                }
-                                _ => format!("git dependency to `{name}`"),
-                            };
-                            tracing::warn!("✅ Successfully removed the {msg}.");
+                        // A network dependency only matches `--network`'s value; a local or git
+                        // dependency has no network of its own, so name alone disambiguates it.
+                        let on_requested_network =
+                            dependency.network().map_or(true, |network| network == &self.network);
+                        if names.contains(dependency.name()) && on_requested_network {
+                            found.insert(dependency.name().clone());
+                            removed.push(dependency.name().clone());
                             None
                         } else {
                             Some(dependency.clone())
@@ -103,16 +179,28 @@ impl Command for Remove {
                 _ => Vec::new(),
             };
 
-            // Throw error if no match is found.
-            if !found_match {
-                return Err(PackageError::dependency_not_found(name).into());
+            // Throw error if any requested name had no match.
+            if let Some(missing) = names.difference(&found).next() {
+                return Err(PackageError::dependency_not_found(missing.clone()).into());
             }
 
             dep
-        } else {
+        };
+
+        // Prune any transitive dependency that is no longer reachable from the remaining
+        // top-level dependencies, unless the caller asked to keep them around.
+        let pruned_orphans = if self.keep_orphans {
             Vec::new()
+        } else {
+            Self::prune_orphaned_dependencies(&path.join("imports"), &dependencies, self.dry_run)?
         };
 
+        Self::report(&self, &dependencies, &removed, &pruned_orphans);
+
+        if self.dry_run {
+            return Ok(());
+        }
+
         // Update the manifest file.
         let new_manifest = Manifest::new(
             manifest.program(),
@@ -125,6 +213,287 @@ impl Command for Remove {
             .map_err(|err| PackageError::failed_to_serialize_manifest_file(path.to_str().unwrap(), err))?;
         std::fs::write(path.join("program.json"), new_manifest_data).map_err(PackageError::failed_to_write_manifest)?;
 
+        // Rewrite `program.lock` to reflect the remaining dependencies.
+        Lockfile::resolve(&new_manifest.dependencies().clone().unwrap_or_default(), &path.join("imports"))?.write(&path)?;
+
+        Ok(())
+    }
+}
+
+impl Remove {
+    /// Validates and normalizes a dependency name, allowing both `credits.aleo` and `credits` syntax.
+    fn normalize_name(name: &str) -> Result<String> {
+        match name {
+            name if name.ends_with(".aleo") && Package::<CurrentNetwork>::is_program_name_valid(&name[0..name.len() - 5]) => {
+                Ok(name.to_string())
+            }
+            name if Package::<CurrentNetwork>::is_program_name_valid(name) => Ok(format!("{name}.aleo")),
+            name => Err(PackageError::invalid_file_name_dependency(name.to_string()).into()),
+        }
+    }
+
+    /// Prints what this invocation did (or, under `--dry-run`, would do) to the remaining
+    /// dependency set, either as human-readable `tracing` messages or as structured JSON.
+    fn report(&self, dependencies: &[Dependency], removed: &[String], pruned_orphans: &[String]) {
+        if self.json {
+            let report = RemoveReport {
+                dependencies: dependencies.iter().map(|dependency| dependency.name().clone()).collect(),
+                removed: removed.to_vec(),
+                pruned_orphans: pruned_orphans.to_vec(),
+                dry_run: self.dry_run,
+            };
+            println!("{}", serde_json::to_string_pretty(&report).expect("a `RemoveReport` is always valid JSON"));
+            return;
+        }
+
+        let prefix = if self.dry_run { "Would have removed" } else { "Successfully removed" };
+        for name in removed {
+            tracing::warn!("✅ {prefix} the dependency to `{name}`.");
+        }
+        let prefix = if self.dry_run { "Would have pruned" } else { "Successfully pruned" };
+        for name in pruned_orphans {
+            tracing::warn!("✅ {prefix} the orphaned transitive dependency `{name}`.");
+        }
+    }
+
+    /// Rewrites every dependency's network field from `from` to `to` in place, leaving the
+    /// dependency set itself untouched. Used to migrate a manifest off a retired network
+    /// (e.g. `testnet3` → `mainnet`) without hand-editing `program.json`.
+    fn migrate_network(path: PathBuf, manifest: Manifest, from: &str, to: &str, dry_run: bool, json: bool) -> Result<()> {
+        let mut migrated = Vec::new();
+        let dependencies: Vec<Dependency> = manifest
+            .dependencies()
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|dependency| match dependency.network() {
+                Some(network) if network == from => {
+                    migrated.push(dependency.name().clone());
+                    Dependency::new(dependency.name().clone(), dependency.path().clone(), Some(to.to_string()))
+                }
+                _ => dependency,
+            })
+            .collect();
+
+        if migrated.is_empty() {
+            return Err(PackageError::dependency_not_found(from.to_string()).into());
+        }
+
+        if json {
+            let report = RemoveReport {
+                dependencies: dependencies.iter().map(|dependency| dependency.name().clone()).collect(),
+                removed: Vec::new(),
+                pruned_orphans: Vec::new(),
+                dry_run,
+            };
+            println!("{}", serde_json::to_string_pretty(&report).expect("a `RemoveReport` is always valid JSON"));
+        } else {
+            let prefix = if dry_run { "Would have migrated" } else { "Migrated" };
+            for name in &migrated {
+                tracing::warn!("✅ {prefix} network dependency `{name}` from `{from}` to `{to}`.");
+            }
+        }
+
+        if dry_run {
+            return Ok(());
+        }
+
+        let new_manifest =
+            Manifest::new(manifest.program(), manifest.version(), manifest.description(), manifest.license(), Some(dependencies));
+        let new_manifest_data = serde_json::to_string_pretty(&new_manifest)
+            .map_err(|err| PackageError::failed_to_serialize_manifest_file(path.to_str().unwrap(), err))?;
+        std::fs::write(path.join("program.json"), new_manifest_data).map_err(PackageError::failed_to_write_manifest)?;
+
+        Lockfile::resolve(&new_manifest.dependencies().clone().unwrap_or_default(), &path.join("imports"))?.write(&path)?;
+
         Ok(())
     }
+
+    /// Builds a directed graph over every program cached under `imports_dir` (an edge `a -> b`
+    /// means program `a` imports program `b`, discovered by parsing its fetched `program.json`),
+    /// then returns the names of every program unreachable from `roots`, removing them from the
+    /// cache unless `dry_run` is set.
+    fn prune_orphaned_dependencies(imports_dir: &Path, roots: &[Dependency], dry_run: bool) -> Result<Vec<String>> {
+        if !imports_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut graph: HashMap<String, Vec<String>> = HashMap::new();
+        for entry in
+            std::fs::read_dir(imports_dir).map_err(|err| PackageError::failed_to_read_file(imports_dir.to_str().unwrap(), err))?
+        {
+            let entry = entry.map_err(|err| PackageError::failed_to_read_file(imports_dir.to_str().unwrap(), err))?;
+            let manifest_path = entry.path().join("program.json");
+            if !manifest_path.is_file() {
+                continue;
+            }
+
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let data = std::fs::read_to_string(&manifest_path)
+                .map_err(|err| PackageError::failed_to_read_file(manifest_path.to_str().unwrap(), err))?;
+            let manifest: Manifest = serde_json::from_str(&data)
+                .map_err(|err| PackageError::failed_to_deserialize_manifest_file(manifest_path.to_str().unwrap(), err))?;
+
+            let edges = manifest.dependencies().clone().unwrap_or_default().iter().map(|dep| dep.name().clone()).collect();
+            graph.insert(name, edges);
+        }
+
+        // Mark everything still reachable from the remaining top-level dependencies.
+        let mut reachable: HashSet<String> = HashSet::new();
+        let mut stack: Vec<String> = roots.iter().map(|dep| dep.name().clone()).collect();
+        while let Some(name) = stack.pop() {
+            if !reachable.insert(name.clone()) {
+                continue;
+            }
+            if let Some(neighbors) = graph.get(&name) {
+                stack.extend(neighbors.iter().cloned());
+            }
+        }
+
+        // Collect everything left unmarked, dropping it from the cache unless this is a dry run.
+        let mut pruned = Vec::new();
+        for name in graph.keys() {
+            if reachable.contains(name) {
+                continue;
+            }
+            if !dry_run {
+                std::fs::remove_dir_all(imports_dir.join(name)).map_err(PackageError::failed_to_remove_directory)?;
+            }
+            pruned.push(name.clone());
+        }
+
+        Ok(pruned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_cached_program(imports_dir: &Path, name: &str, dependencies: &[&str]) {
+        let program_dir = imports_dir.join(name);
+        std::fs::create_dir_all(&program_dir).unwrap();
+        let manifest = Manifest::new(
+            name,
+            "0.1.0",
+            "",
+            "",
+            Some(dependencies.iter().map(|dep| Dependency::new((*dep).to_string(), None, None)).collect()),
+        );
+        std::fs::write(program_dir.join("program.json"), serde_json::to_string_pretty(&manifest).unwrap()).unwrap();
+    }
+
+    fn root_dependency(name: &str) -> Dependency {
+        Dependency::new(name.to_string(), None, None)
+    }
+
+    #[test]
+    fn prune_orphaned_dependencies_removes_only_unreachable_programs() {
+        let imports_dir = std::env::temp_dir().join(format!("leo-remove-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&imports_dir);
+
+        // `root.aleo` (kept) -> `kept_child.aleo`, while `orphan.aleo` has no incoming edge.
+        write_cached_program(&imports_dir, "root.aleo", &["kept_child.aleo"]);
+        write_cached_program(&imports_dir, "kept_child.aleo", &[]);
+        write_cached_program(&imports_dir, "orphan.aleo", &[]);
+
+        let roots = vec![root_dependency("root.aleo")];
+        let pruned = Remove::prune_orphaned_dependencies(&imports_dir, &roots, false).unwrap();
+
+        assert_eq!(pruned, vec!["orphan.aleo".to_string()]);
+        assert!(imports_dir.join("kept_child.aleo").is_dir());
+        assert!(!imports_dir.join("orphan.aleo").exists());
+
+        std::fs::remove_dir_all(&imports_dir).unwrap();
+    }
+
+    #[test]
+    fn prune_orphaned_dependencies_dry_run_leaves_cache_untouched() {
+        let imports_dir = std::env::temp_dir().join(format!("leo-remove-test-dry-run-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&imports_dir);
+
+        write_cached_program(&imports_dir, "orphan.aleo", &[]);
+
+        let pruned = Remove::prune_orphaned_dependencies(&imports_dir, &[], true).unwrap();
+
+        assert_eq!(pruned, vec!["orphan.aleo".to_string()]);
+        assert!(imports_dir.join("orphan.aleo").is_dir(), "dry-run must not touch the import cache");
+
+        std::fs::remove_dir_all(&imports_dir).unwrap();
+    }
+
+    #[test]
+    fn normalize_name_accepts_both_bare_and_suffixed_names() {
+        assert_eq!(Remove::normalize_name("credits").unwrap(), "credits.aleo");
+        assert_eq!(Remove::normalize_name("credits.aleo").unwrap(), "credits.aleo");
+    }
+
+    #[test]
+    fn normalize_name_rejects_an_invalid_program_name() {
+        assert!(Remove::normalize_name("not a valid name!").is_err());
+    }
+
+    #[test]
+    fn migrate_network_rewrites_only_dependencies_on_the_source_network() {
+        let path = std::env::temp_dir().join(format!("leo-remove-migrate-test-{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+
+        let dependencies = vec![
+            Dependency::new("a.aleo".to_string(), None, Some("testnet3".to_string())),
+            Dependency::new("b.aleo".to_string(), None, Some("devnet".to_string())),
+        ];
+        let manifest = Manifest::new("root.aleo", "0.1.0", "", "", Some(dependencies));
+
+        Remove::migrate_network(path.clone(), manifest, "testnet3", "mainnet", false, false).unwrap();
+
+        let updated: Manifest = serde_json::from_str(&std::fs::read_to_string(path.join("program.json")).unwrap()).unwrap();
+        let updated_dependencies = updated.dependencies().clone().unwrap();
+        assert_eq!(updated_dependencies.iter().find(|dep| dep.name() == "a.aleo").unwrap().network(), Some(&"mainnet".to_string()));
+        assert_eq!(
+            updated_dependencies.iter().find(|dep| dep.name() == "b.aleo").unwrap().network(),
+            Some(&"devnet".to_string()),
+            "a dependency on an unrelated network must be left untouched"
+        );
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn migrate_network_errors_when_nothing_matches_the_source_network() {
+        let path = std::env::temp_dir().join(format!("leo-remove-migrate-test-noop-{}", std::process::id()));
+        let dependencies = vec![Dependency::new("a.aleo".to_string(), None, Some("mainnet".to_string()))];
+        let manifest = Manifest::new("root.aleo", "0.1.0", "", "", Some(dependencies));
+
+        assert!(Remove::migrate_network(path, manifest, "testnet3", "mainnet", true, false).is_err());
+    }
+
+    #[test]
+    fn migrate_network_dry_run_does_not_write_program_json() {
+        let path = std::env::temp_dir().join(format!("leo-remove-migrate-test-dry-run-{}", std::process::id()));
+        std::fs::create_dir_all(&path).unwrap();
+
+        let dependencies = vec![Dependency::new("a.aleo".to_string(), None, Some("testnet3".to_string()))];
+        let manifest = Manifest::new("root.aleo", "0.1.0", "", "", Some(dependencies));
+
+        Remove::migrate_network(path.clone(), manifest, "testnet3", "mainnet", true, false).unwrap();
+
+        assert!(!path.join("program.json").exists(), "a dry run must not write `program.json`");
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn remove_report_serializes_the_dry_run_flag_and_pruned_orphans() {
+        let report = RemoveReport {
+            dependencies: vec!["kept.aleo".to_string()],
+            removed: vec!["removed.aleo".to_string()],
+            pruned_orphans: vec!["orphan.aleo".to_string()],
+            dry_run: true,
+        };
+
+        let json: serde_json::Value = serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+        assert_eq!(json["dry_run"], true);
+        assert_eq!(json["removed"], serde_json::json!(["removed.aleo"]));
+        assert_eq!(json["pruned_orphans"], serde_json::json!(["orphan.aleo"]));
+    }
 }
@@ -0,0 +1,293 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+
+/// The GitHub repository that releases are fetched from.
+const REPO_OWNER: &str = "ProvableHQ";
+const REPO_NAME: &str = "leo";
+
+/// An asset attached to a GitHub release.
+#[derive(Debug, Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// A single GitHub release.
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<ReleaseAsset>,
+}
+
+/// Update the Leo CLI to the latest (or a pinned) version.
+#[derive(Parser, Debug)]
+#[clap(name = "leo", author = "The Aleo Team <hello@aleo.org>", version)]
+pub struct Update {
+    #[clap(name = "VERSION", help = "A specific version to install. Ex: `1.9.4`. Defaults to the latest release.")]
+    pub(crate) version: Option<String>,
+
+    #[clap(short = 'l', long, help = "Lists all available versions of Leo")]
+    pub(crate) list: bool,
+
+    #[clap(short = 'q', long, help = "Suppress outputs to terminal")]
+    pub(crate) quiet: bool,
+}
+
+impl Command for Update {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Leo")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, _: Context, _: Self::Input) -> Result<Self::Output> {
+        let releases = Self::fetch_releases()?;
+
+        if self.list {
+            for release in &releases {
+                println!("{}", release.tag_name);
+            }
+            return Ok(());
+        }
+
+        let current = env!("CARGO_PKG_VERSION");
+        let current_version =
+            semver::Version::parse(current).map_err(|err| PackageError::failed_to_parse_version(current, err))?;
+
+        let target = match &self.version {
+            Some(version) => releases
+                .into_iter()
+                .find(|release| release.tag_name.trim_start_matches('v') == version.trim_start_matches('v'))
+                .ok_or_else(|| PackageError::version_not_found(version.clone()))?,
+            None => releases
+                .into_iter()
+                .max_by_key(|release| semver::Version::parse(release.tag_name.trim_start_matches('v')).ok())
+                .ok_or_else(PackageError::no_releases_found)?,
+        };
+
+        let target_version = semver::Version::parse(target.tag_name.trim_start_matches('v'))
+            .map_err(|err| PackageError::failed_to_parse_version(&target.tag_name, err))?;
+
+        if target_version <= current_version && self.version.is_none() {
+            if !self.quiet {
+                tracing::info!("✅ Leo is already up to date (v{current_version}).");
+            }
+            return Ok(());
+        }
+
+        let asset_name = Self::asset_name_for_platform();
+        let asset = target
+            .assets
+            .iter()
+            .find(|asset| asset.name == asset_name)
+            .ok_or_else(|| PackageError::no_release_asset_for_platform(asset_name.clone()))?;
+        let checksum_asset = target
+            .assets
+            .iter()
+            .find(|asset| asset.name == format!("{asset_name}.sha256"))
+            .ok_or_else(|| PackageError::no_release_asset_for_platform(format!("{asset_name}.sha256")))?;
+
+        if !self.quiet {
+            tracing::info!("⬇️  Downloading Leo v{target_version} from `{}`...", asset.browser_download_url);
+        }
+
+        let expected_checksum = Self::fetch_checksum(&checksum_asset.browser_download_url)?;
+        let archive = Self::download_asset(&asset.browser_download_url, &expected_checksum)?;
+        let binary = Self::extract_binary(&archive)?;
+        Self::swap_executable(&binary)?;
+
+        if !self.quiet {
+            tracing::info!("✅ Successfully updated Leo to v{target_version}.");
+        }
+
+        Ok(())
+    }
+}
+
+impl Update {
+    /// Fetches the list of releases from the GitHub releases API.
+    fn fetch_releases() -> Result<Vec<Release>> {
+        let url = format!("https://api.github.com/repos/{REPO_OWNER}/{REPO_NAME}/releases");
+        let response =
+            ureq::get(&url).set("User-Agent", "leo-update").call().map_err(PackageError::failed_to_retrieve_from_endpoint)?;
+
+        response.into_json::<Vec<Release>>().map_err(PackageError::failed_to_retrieve_from_endpoint)
+    }
+
+    /// Returns the name of the release asset matching the current platform.
+    fn asset_name_for_platform() -> String {
+        let os = if cfg!(target_os = "macos") {
+            "macos"
+        } else if cfg!(target_os = "windows") {
+            "windows"
+        } else {
+            "linux"
+        };
+        let arch = if cfg!(target_arch = "aarch64") { "aarch64" } else { "x86_64" };
+        let ext = if cfg!(target_os = "windows") { "zip" } else { "tar.gz" };
+
+        format!("leo-{os}-{arch}.{ext}")
+    }
+
+    /// Fetches the published `<asset>.sha256` file and returns the hex digest it contains.
+    fn fetch_checksum(url: &str) -> Result<String> {
+        let response =
+            ureq::get(url).set("User-Agent", "leo-update").call().map_err(PackageError::failed_to_retrieve_from_endpoint)?;
+        let body = response.into_string().map_err(PackageError::failed_to_retrieve_from_endpoint)?;
+
+        // Checksum files conventionally look like `<digest>  <filename>`.
+        Ok(body.split_whitespace().next().unwrap_or_default().to_lowercase())
+    }
+
+    /// Streams the asset at `url` into memory and verifies it against `expected_checksum`
+    /// before handing it back, so a corrupted or tampered download is rejected up front.
+    fn download_asset(url: &str, expected_checksum: &str) -> Result<Vec<u8>> {
+        let response =
+            ureq::get(url).set("User-Agent", "leo-update").call().map_err(PackageError::failed_to_retrieve_from_endpoint)?;
+
+        let mut buffer = Vec::new();
+        response.into_reader().read_to_end(&mut buffer).map_err(PackageError::failed_to_retrieve_from_endpoint)?;
+
+        let actual_checksum = format!("{:x}", Sha256::digest(&buffer));
+        if !actual_checksum.eq_ignore_ascii_case(expected_checksum) {
+            return Err(PackageError::asset_checksum_mismatch(url.to_string()).into());
+        }
+
+        Ok(buffer)
+    }
+
+    /// Extracts the `leo` executable out of a downloaded release archive (a `.tar.gz` on
+    /// Unix, a `.zip` on Windows) and returns its raw bytes.
+    #[cfg(not(windows))]
+    fn extract_binary(archive_bytes: &[u8]) -> Result<Vec<u8>> {
+        let decoder = flate2::read::GzDecoder::new(archive_bytes);
+        let mut archive = tar::Archive::new(decoder);
+
+        for entry in archive.entries().map_err(PackageError::failed_to_extract_archive)? {
+            let mut entry = entry.map_err(PackageError::failed_to_extract_archive)?;
+            let path = entry.path().map_err(PackageError::failed_to_extract_archive)?;
+            if path.file_name().map(|name| name == "leo").unwrap_or(false) {
+                let mut buffer = Vec::new();
+                entry.read_to_end(&mut buffer).map_err(PackageError::failed_to_extract_archive)?;
+                return Ok(buffer);
+            }
+        }
+
+        Err(PackageError::no_release_asset_for_platform("leo".to_string()).into())
+    }
+
+    /// Extracts the `leo.exe` executable out of a downloaded release archive.
+    #[cfg(windows)]
+    fn extract_binary(archive_bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut archive =
+            zip::ZipArchive::new(std::io::Cursor::new(archive_bytes)).map_err(PackageError::failed_to_extract_archive)?;
+        let mut file = archive.by_name("leo.exe").map_err(|_| PackageError::no_release_asset_for_platform("leo.exe".to_string()))?;
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).map_err(PackageError::failed_to_extract_archive)?;
+        Ok(buffer)
+    }
+
+    /// Atomically replaces the running executable with the freshly extracted `binary`.
+    fn swap_executable(binary: &[u8]) -> Result<()> {
+        let current_exe = std::env::current_exe().map_err(PackageError::failed_to_locate_executable)?;
+        let download_path = current_exe.with_extension("update");
+
+        let mut file = std::fs::File::create(&download_path).map_err(PackageError::failed_to_write_file)?;
+        file.write_all(binary).map_err(PackageError::failed_to_write_file)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut permissions = file.metadata().map_err(PackageError::failed_to_write_file)?.permissions();
+            permissions.set_mode(0o755);
+            std::fs::set_permissions(&download_path, permissions).map_err(PackageError::failed_to_write_file)?;
+        }
+
+        #[cfg(windows)]
+        {
+            let old_aside = current_exe.with_extension("old");
+            std::fs::rename(&current_exe, &old_aside).map_err(PackageError::failed_to_write_file)?;
+        }
+
+        std::fs::rename(&download_path, &current_exe).map_err(PackageError::failed_to_write_file)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asset_name_for_platform_uses_the_expected_extension_per_os() {
+        let name = Update::asset_name_for_platform();
+        let expected_ext = if cfg!(target_os = "windows") { "zip" } else { "tar.gz" };
+        assert!(name.starts_with("leo-"), "asset name should start with `leo-`, got `{name}`");
+        assert!(name.ends_with(expected_ext), "asset name `{name}` should end with `.{expected_ext}`");
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn extract_binary_finds_the_leo_entry_in_a_tar_gz_archive() {
+        use std::io::Write as _;
+
+        let mut builder = tar::Builder::new(Vec::new());
+        let contents = b"#!/bin/sh\necho leo\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_cksum();
+        builder.append_data(&mut header, "leo", &contents[..]).unwrap();
+        let tar_bytes = builder.into_inner().unwrap();
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&tar_bytes).unwrap();
+        let archive_bytes = encoder.finish().unwrap();
+
+        let extracted = Update::extract_binary(&archive_bytes).unwrap();
+        assert_eq!(extracted, contents);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn extract_binary_errors_when_no_leo_entry_is_present() {
+        use std::io::Write as _;
+
+        let mut builder = tar::Builder::new(Vec::new());
+        let contents = b"not the binary you're looking for";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_cksum();
+        builder.append_data(&mut header, "README.md", &contents[..]).unwrap();
+        let tar_bytes = builder.into_inner().unwrap();
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&tar_bytes).unwrap();
+        let archive_bytes = encoder.finish().unwrap();
+
+        assert!(Update::extract_binary(&archive_bytes).is_err());
+    }
+}
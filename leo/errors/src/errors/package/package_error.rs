@@ -0,0 +1,143 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::create_messages;
+
+create_messages!(
+    /// PackageError enum that represents all the errors for the `leo-package` crate.
+    PackageError,
+    code_mask: 4000i32 * 1000i32,
+    code_prefix: "PAK",
+
+    @backtraced
+    failed_to_read_file {
+        args: (path: impl Display, error: impl ErrorArg),
+        msg: format!("Failed to read file from the provided path - {path}.\n{error}"),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_deserialize_manifest_file {
+        args: (path: impl Display, error: impl ErrorArg),
+        msg: format!("Failed to deserialize the manifest file at `{path}`.\n{error}"),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_serialize_manifest_file {
+        args: (path: impl Display, error: impl ErrorArg),
+        msg: format!("Failed to serialize the manifest file at `{path}`.\n{error}"),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_write_manifest {
+        args: (error: impl ErrorArg),
+        msg: format!("Failed to write the manifest file.\n{error}"),
+        help: None,
+    }
+
+    @backtraced
+    invalid_file_name_dependency {
+        args: (name: impl Display),
+        msg: format!("`{name}` is not a valid dependency name."),
+        help: None,
+    }
+
+    @backtraced
+    dependency_not_found {
+        args: (name: impl Display),
+        msg: format!("Could not find a dependency matching `{name}`."),
+        help: None,
+    }
+
+    @backtraced
+    manifest_lockfile_mismatch {
+        args: (path: impl Display),
+        msg: format!("`{path}` is out of sync with `program.json`; delete it and the next `leo remove` will write a fresh one."),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_retrieve_from_endpoint {
+        args: (error: impl ErrorArg),
+        msg: format!("Failed to retrieve data from the release endpoint.\n{error}"),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_locate_executable {
+        args: (error: impl ErrorArg),
+        msg: format!("Failed to locate the currently running executable.\n{error}"),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_parse_version {
+        args: (version: impl Display, error: impl ErrorArg),
+        msg: format!("Failed to parse `{version}` as a semantic version.\n{error}"),
+        help: None,
+    }
+
+    @backtraced
+    no_releases_found {
+        args: (),
+        msg: "No releases were found for the Leo repository.".to_string(),
+        help: None,
+    }
+
+    @backtraced
+    version_not_found {
+        args: (version: impl Display),
+        msg: format!("Could not find a release matching version `{version}`."),
+        help: None,
+    }
+
+    @backtraced
+    no_release_asset_for_platform {
+        args: (asset_name: impl Display),
+        msg: format!("The selected release has no asset named `{asset_name}` for this platform."),
+        help: None,
+    }
+
+    @backtraced
+    asset_checksum_mismatch {
+        args: (url: impl Display),
+        msg: format!("The asset downloaded from `{url}` did not match its published checksum."),
+        help: Some("The download may have been corrupted or tampered with in transit; try running `leo update` again.".to_string()),
+    }
+
+    @backtraced
+    failed_to_extract_archive {
+        args: (error: impl ErrorArg),
+        msg: format!("Failed to extract the `leo` executable from the downloaded release archive.\n{error}"),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_write_file {
+        args: (error: impl ErrorArg),
+        msg: format!("Failed to write to disk.\n{error}"),
+        help: None,
+    }
+
+    @backtraced
+    failed_to_remove_directory {
+        args: (error: impl ErrorArg),
+        msg: format!("Failed to remove a cached directory.\n{error}"),
+        help: None,
+    }
+);
@@ -0,0 +1,53 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::create_messages;
+use leo_span::Span;
+
+create_messages!(
+    /// TypeCheckerError enum that represents all the errors for the `leo-type-checker` crate.
+    TypeCheckerError,
+    code_mask: 2000i32 * 1000i32,
+    code_prefix: "TYC",
+
+    @formatted
+    illegal_recursive_call {
+        args: (span: Span),
+        msg: "Functions cannot call themselves, directly or transitively through other functions, unless every function on the cycle is `inline`.".to_string(),
+        help: None,
+    }
+
+    @formatted
+    async_transition_missing_finalize {
+        args: (span: Span),
+        msg: "An `async transition` must have a corresponding `finalize` block.".to_string(),
+        help: None,
+    }
+
+    @formatted
+    finalize_input_mismatch {
+        args: (span: Span),
+        msg: "The number of arguments this function passes to its `finalize` block does not match the number of inputs that block expects.".to_string(),
+        help: None,
+    }
+
+    @formatted
+    unreachable_finalize_block {
+        args: (span: Span),
+        msg: "This `finalize` block is unreachable from any program entry point.".to_string(),
+        help: None,
+    }
+);